@@ -1,78 +1,635 @@
 use ndarray::prelude::*;
+use num_complex::Complex64;
 use plotters::prelude::*;
 use std::f64::consts::PI;
 
-// Define wavelength range (in nm) from 500 to 800, resolution 0.5 nm
-let wavelengths: Array1<f64> = Array::range(200.0, 800.5, 0.5);
+// Dispersion models for the real refractive index n(λ). Cauchy only fits narrow bands; Sellmeier
+// (λ in µm) stays accurate across the full UV-to-NIR range used here. Cauchy is kept as the legacy
+// fit the Sellmeier presets below are benchmarked against in dispersion_tests, not for use in the
+// forward model.
+enum Dispersion {
+    #[allow(dead_code)]
+    Cauchy { a: f64, b: f64 },
+    Sellmeier { b: [f64; 3], c: [f64; 3] },
+}
+
+impl Dispersion {
+    // n(λ) generated on the given wavelength grid (nm)
+    fn refractive_index(&self, wavelengths_nm: &Array1<f64>) -> Array1<f64> {
+        match self {
+            Dispersion::Cauchy { a, b } => wavelengths_nm.mapv(|w| a + b / (w * w)),
+            Dispersion::Sellmeier { b, c } => wavelengths_nm.mapv(|w_nm| {
+                let w_um_sq = (w_nm * 1e-3).powi(2);
+                let n_sq = 1.0 + (0..3).map(|j| b[j] * w_um_sq / (w_um_sq - c[j])).sum::<f64>();
+                n_sq.sqrt()
+            }),
+        }
+    }
+}
+
+// Named dispersion presets so published coefficients can be dropped in directly
+const SIO2_SELLMEIER: Dispersion = Dispersion::Sellmeier {
+    b: [0.6961663, 0.4079426, 0.8974794],
+    c: [0.0046791, 0.0135121, 97.934003],
+}; // fused silica, Malitson (1965)
+
+// Not used by the forward model in main, but kept ready as a drop-in coating material and
+// exercised by dispersion_tests.
+#[allow(dead_code)]
+const TIO2_SELLMEIER: Dispersion = Dispersion::Sellmeier {
+    b: [4.913, 0.2441, 0.0],
+    c: [0.0, 0.0803, 1.0],
+}; // anatase TiO2, approximate fit (DeVore 1951: n² = 5.913 + 0.2441λ²/(λ²−0.0803), so B₀ = 5.913 − 1)
+
+#[allow(dead_code)]
+const SI3N4_SELLMEIER: Dispersion = Dispersion::Sellmeier {
+    b: [3.0249, 40314.0, 0.0],
+    c: [0.01831708, 1537208.18, 1.0],
+}; // stoichiometric Si3N4, approximate fit (Luke et al. 2015)
+
+#[cfg(test)]
+mod dispersion_tests {
+    use super::*;
+
+    // Exercise every preset against its published reference point at 200 nm, where the legacy
+    // Cauchy fit is known to drift from Sellmeier (the reason it was replaced as the thin-film
+    // model in main).
+    #[test]
+    fn refractive_index_at_200nm_matches_reference_points() {
+        let w = Array1::from(vec![200.0]);
+        let legacy_cauchy_fit = Dispersion::Cauchy { a: 1.458, b: 0.00354 };
+
+        assert!((legacy_cauchy_fit.refractive_index(&w)[0] - 1.4580).abs() < 1e-4);
+        assert!((SIO2_SELLMEIER.refractive_index(&w)[0] - 1.5505).abs() < 1e-4);
+        assert!((TIO2_SELLMEIER.refractive_index(&w)[0] - 2.3813).abs() < 1e-4);
+        assert!((SI3N4_SELLMEIER.refractive_index(&w)[0] - 2.5650).abs() < 1e-4);
+    }
+}
+
+// Combine a real refractive index n(λ) and extinction coefficient k(λ) into n + ik
+fn complex_index(n: &Array1<f64>, k: &Array1<f64>) -> Array1<Complex64> {
+    n.iter().zip(k.iter()).map(|(&n, &k)| Complex64::new(n, k)).collect()
+}
+
+// Approximate crystalline-silicon extinction coefficient: strong absorption below ~400 nm,
+// falling off toward the visible/NIR where silicon is nearly transparent at thin-film scales
+fn silicon_k(wavelength_nm: f64) -> f64 {
+    if wavelength_nm < 400.0 {
+        0.05 + 3.5 * ((400.0 - wavelength_nm) / 200.0).min(1.0)
+    } else {
+        0.01
+    }
+}
 
-// Cauchy's equation coefficients (defined for any material)
-let a = 1.458;
-let b = 0.00354;
+// Multiply two 2x2 complex characteristic matrices (row-major, [[m00, m01], [m10, m11]])
+fn mat2_mul(a: [[Complex64; 2]; 2], b: [[Complex64; 2]; 2]) -> [[Complex64; 2]; 2] {
+    [
+        [
+            a[0][0] * b[0][0] + a[0][1] * b[1][0],
+            a[0][0] * b[0][1] + a[0][1] * b[1][1],
+        ],
+        [
+            a[1][0] * b[0][0] + a[1][1] * b[1][0],
+            a[1][0] * b[0][1] + a[1][1] * b[1][1],
+        ],
+    ]
+}
 
-// Quantify refractive index (n)
-let n_thin_film: Array1<f64> = &wavelengths.mapv(|w| a + b / (w * w));
+// s- or p-polarized light; the unpolarized average of both comes from reflectivity_s_p_unpolarized
+#[derive(Clone, Copy)]
+enum Polarization {
+    S,
+    P,
+}
 
-// Refractive index for base material (constant for simplicity)
-let n_si = 3.5;
+// Angle θ in a medium of index `n`, given the incident medium's index `n0` and sin(θ0), via
+// Snell's law n0·sinθ0 = n·sinθ. Returned as cosθ, which is what the admittances need.
+fn snell_cos_theta(n0: Complex64, sin_theta0: f64, n: Complex64) -> Complex64 {
+    let sin_theta = n0 * Complex64::new(sin_theta0, 0.0) / n;
+    (Complex64::new(1.0, 0.0) - sin_theta * sin_theta).sqrt()
+}
 
-// Air
-let n_air = 1.0;
+// Tilted optical admittance: η = n·cosθ for s-polarization, η = n/cosθ for p-polarization
+fn admittance(n: Complex64, cos_theta: Complex64, polarization: Polarization) -> Complex64 {
+    match polarization {
+        Polarization::S => n * cos_theta,
+        Polarization::P => n / cos_theta,
+    }
+}
 
-// Reflectivity using the transfer matrix method to account for interference and multiple reflections
+// Reflectivity of an arbitrary N-layer stack via the standard characteristic-matrix method, at
+// incidence angle `theta0_deg` and a chosen polarization. `layers` lists each film as (complex
+// refractive index over `wavelengths`, thickness in Å), in order from the incident medium toward
+// the substrate. `n_substrate` is given per wavelength so absorbing substrates (e.g. silicon in
+// the UV) are handled correctly.
 fn transfer_matrix_reflectivity(
-    n_air: f64,
-    n_thin_film: &Array1<f64>,
-    n_silicon: f64,
-    thickness: f64,
+    n_incident: Complex64,
+    layers: &[(Array1<Complex64>, f64)],
+    n_substrate: &Array1<Complex64>,
     wavelengths: &Array1<f64>,
+    theta0_deg: f64,
+    polarization: Polarization,
 ) -> Array1<f64> {
-    // Å to nm
-    let thickness_nm = thickness * 1e-1;
+    // The layer matrix below uses the exp(+iωt) sign convention, in which an absorbing medium has
+    // index n − ik; the n + ik inputs are conjugated to match, so that k attenuates.
+    let n_incident = n_incident.conj();
+    let sin_theta0 = theta0_deg.to_radians().sin();
+
+    wavelengths
+        .iter()
+        .enumerate()
+        .map(|(i, &lambda)| {
+            // Product of the per-layer characteristic matrices, incident side first
+            let mut m = [
+                [Complex64::new(1.0, 0.0), Complex64::new(0.0, 0.0)],
+                [Complex64::new(0.0, 0.0), Complex64::new(1.0, 0.0)],
+            ];
+
+            for (index, thickness) in layers {
+                // Å to nm
+                let thickness_nm = thickness * 1e-1;
+                let n_layer = index[i].conj();
+                let cos_theta = snell_cos_theta(n_incident, sin_theta0, n_layer);
 
-    // Phase change on reflection
-    let delta: Array1<f64> = (2.0 * PI / wavelengths) * n_thin_film * thickness_nm;
+                // Tilted optical admittance
+                let eta = admittance(n_layer, cos_theta, polarization);
 
-    // Reflectivity using transfer matrix method
-    let r01: Array1<f64> = (&n_air - n_thin_film) / (&n_air + n_thin_film);
-    let r12: Array1<f64> = (n_thin_film - n_silicon) / (n_thin_film + n_silicon);
+                // Phase thickness, picking up the cosθ factor off normal incidence
+                let delta = Complex64::new(2.0 * PI * thickness_nm / lambda, 0.0) * n_layer * cos_theta;
 
-    let r: Array1<f64> = (&r01 + &r12 * &delta.mapv(|d| (-2.0 * d * 1.0_f64).exp())) /
-        (&Array::ones(wavelengths.len()) + &r01 * &r12 * &delta.mapv(|d| (-2.0 * d * 1.0_f64).exp()));
+                let layer_matrix = [
+                    [delta.cos(), Complex64::i() * delta.sin() / eta],
+                    [Complex64::i() * eta * delta.sin(), delta.cos()],
+                ];
 
-    r.mapv(|r| r.abs().powi(2))
+                m = mat2_mul(m, layer_matrix);
+            }
+
+            let cos_theta0 = Complex64::new(theta0_deg.to_radians().cos(), 0.0);
+            let n_sub = n_substrate[i].conj();
+            let cos_theta_s = snell_cos_theta(n_incident, sin_theta0, n_sub);
+
+            let eta0 = admittance(n_incident, cos_theta0, polarization);
+            let etas = admittance(n_sub, cos_theta_s, polarization);
+
+            let numerator = eta0 * m[0][0] + eta0 * etas * m[0][1] - m[1][0] - etas * m[1][1];
+            let denominator = eta0 * m[0][0] + eta0 * etas * m[0][1] + m[1][0] + etas * m[1][1];
+
+            (numerator / denominator).norm_sqr()
+        })
+        .collect()
 }
 
-// Initialize arrays to store reflectivity spectra for different thicknesses
-let thicknesses: Array1<f64> = Array::range(0.0, 6001.0, 1.0); // Thickness range from 0 Å to 6000 Å in steps of 1 Å
-let mut reflectivity_spectra: Array2<f64> = Array2::zeros((thicknesses.len(), wavelengths.len()));
+// Convenience wrapper returning (R_s, R_p, unpolarized average) for unpolarized measurements
+fn reflectivity_s_p_unpolarized(
+    n_incident: Complex64,
+    layers: &[(Array1<Complex64>, f64)],
+    n_substrate: &Array1<Complex64>,
+    wavelengths: &Array1<f64>,
+    theta0_deg: f64,
+) -> (Array1<f64>, Array1<f64>, Array1<f64>) {
+    let r_s = transfer_matrix_reflectivity(n_incident, layers, n_substrate, wavelengths, theta0_deg, Polarization::S);
+    let r_p = transfer_matrix_reflectivity(n_incident, layers, n_substrate, wavelengths, theta0_deg, Polarization::P);
+    let r_unpolarized = (&r_s + &r_p) / 2.0;
 
-// Calculate reflectivity spectra for each thickness
-for (i, &thickness) in thicknesses.iter().enumerate() {
-    reflectivity_spectra.row_mut(i).assign(&transfer_matrix_reflectivity(n_air, &n_thin_film, n_si, thickness, &wavelengths));
+    (r_s, r_p, r_unpolarized)
 }
 
-// Plot reflectivity spectra for selected thicknesses
-let root = BitMapBackend::new("reflectivity_spectra.png", (1200, 800)).into_drawing_area();
-root.fill(&WHITE).unwrap();
-let mut chart = ChartBuilder::on(&root)
-    .caption("Reflectivity Spectra of stack", ("sans-serif", 50).into_font())
-    .margin(10)
-    .x_label_area_size(30)
-    .y_label_area_size(30)
-    .build_cartesian_2d(200.0..800.0, 0.0..1.0)
-    .unwrap();
+#[cfg(test)]
+mod angle_tests {
+    use super::*;
+
+    // Bare air/dielectric interface (no film) at 60° should reduce to the textbook two-medium
+    // Fresnel equations, which exercises Snell's law and the s/p admittance split away from the
+    // degenerate θ0 = 0 case where both polarizations coincide. Reference values hand-computed
+    // for n1 = 1.0 (air), n2 = 1.5, θ0 = 60° (Hecht, Optics, eqs. 4.42/4.43).
+    #[test]
+    fn fresnel_reflectance_at_60_degrees_matches_hand_computation() {
+        let n_incident = Complex64::new(1.0, 0.0);
+        let n_substrate = Array1::from(vec![Complex64::new(1.5, 0.0)]);
+        let wavelengths = Array1::from(vec![550.0]);
+
+        let (r_s, r_p, _) = reflectivity_s_p_unpolarized(n_incident, &[], &n_substrate, &wavelengths, 60.0);
+
+        assert!((r_s[0] - 0.1765).abs() < 1e-3, "R_s = {}", r_s[0]);
+        assert!((r_p[0] - 0.0018).abs() < 1e-3, "R_p = {}", r_p[0]);
+    }
+}
+
+// CIE 1931 2° color-matching functions and the D65 illuminant, tabulated at 10 nm from 380–780 nm
+const CMF_WAVELENGTHS: [f64; 41] = [
+    380.0, 390.0, 400.0, 410.0, 420.0, 430.0, 440.0, 450.0, 460.0, 470.0, 480.0, 490.0, 500.0,
+    510.0, 520.0, 530.0, 540.0, 550.0, 560.0, 570.0, 580.0, 590.0, 600.0, 610.0, 620.0, 630.0,
+    640.0, 650.0, 660.0, 670.0, 680.0, 690.0, 700.0, 710.0, 720.0, 730.0, 740.0, 750.0, 760.0,
+    770.0, 780.0,
+];
+const CMF_X_BAR: [f64; 41] = [
+    0.0014, 0.0042, 0.0143, 0.0435, 0.1344, 0.2839, 0.3483, 0.3362, 0.2908, 0.1954, 0.0956,
+    0.0320, 0.0049, 0.0093, 0.0633, 0.1655, 0.2904, 0.4334, 0.5945, 0.7621, 0.9163, 1.0263,
+    1.0622, 1.0026, 0.8544, 0.6424, 0.4479, 0.2835, 0.1649, 0.0874, 0.0468, 0.0227, 0.0114,
+    0.0058, 0.0029, 0.0014, 0.0007, 0.0003, 0.0002, 0.0001, 0.0000,
+];
+const CMF_Y_BAR: [f64; 41] = [
+    0.0000, 0.0001, 0.0004, 0.0012, 0.0040, 0.0116, 0.0230, 0.0380, 0.0600, 0.0910, 0.1390,
+    0.2080, 0.3230, 0.5030, 0.7100, 0.8620, 0.9540, 0.9950, 0.9950, 0.9520, 0.8700, 0.7570,
+    0.6310, 0.5030, 0.3810, 0.2650, 0.1750, 0.1070, 0.0610, 0.0320, 0.0170, 0.0082, 0.0041,
+    0.0021, 0.0010, 0.0005, 0.0002, 0.0001, 0.0001, 0.0000, 0.0000,
+];
+const CMF_Z_BAR: [f64; 41] = [
+    0.0065, 0.0201, 0.0679, 0.2074, 0.6456, 1.3856, 1.7471, 1.7721, 1.6692, 1.2876, 0.8130,
+    0.4652, 0.2720, 0.1582, 0.0782, 0.0422, 0.0203, 0.0087, 0.0039, 0.0021, 0.0017, 0.0011,
+    0.0008, 0.0003, 0.0002, 0.0000, 0.0000, 0.0000, 0.0000, 0.0000, 0.0000, 0.0000, 0.0000,
+    0.0000, 0.0000, 0.0000, 0.0000, 0.0000, 0.0000, 0.0000, 0.0000,
+];
+const ILLUMINANT_D65: [f64; 41] = [
+    49.98, 54.65, 82.75, 91.49, 93.43, 86.68, 104.86, 117.01, 117.81, 114.86, 115.92, 108.81,
+    109.35, 107.80, 104.79, 107.69, 104.41, 104.05, 100.00, 96.33, 95.79, 88.69, 90.01, 89.60,
+    87.70, 83.29, 83.70, 80.03, 80.21, 82.28, 78.28, 69.72, 71.61, 74.35, 61.60, 69.89, 75.09,
+    63.59, 46.42, 66.81, 63.38,
+];
+
+// How to extend a tabulated spectrum beyond the range it was measured/defined over
+#[derive(Clone, Copy)]
+enum Extrapolation {
+    // Hold the nearest edge value (appropriate for a measured reflectance spectrum)
+    Clamp,
+    // Treat out-of-range samples as zero (appropriate for the CIE CMFs/illuminant, which are
+    // only defined over the visible range and do not plausibly extend as a flat non-zero value)
+    Zero,
+}
+
+// Linearly interpolate a tabulated spectrum onto an arbitrary wavelength grid, extending beyond
+// the table's range per `extrapolation`.
+fn interpolate_table(
+    table_wavelengths: &[f64],
+    table_values: &[f64],
+    wavelengths: &Array1<f64>,
+    extrapolation: Extrapolation,
+) -> Array1<f64> {
+    wavelengths.mapv(|w| {
+        if w <= table_wavelengths[0] {
+            return match extrapolation {
+                Extrapolation::Clamp => table_values[0],
+                Extrapolation::Zero => 0.0,
+            };
+        }
+        if w >= *table_wavelengths.last().unwrap() {
+            return match extrapolation {
+                Extrapolation::Clamp => *table_values.last().unwrap(),
+                Extrapolation::Zero => 0.0,
+            };
+        }
+
+        let hi = table_wavelengths.iter().position(|&tw| tw > w).unwrap();
+        let (w0, w1) = (table_wavelengths[hi - 1], table_wavelengths[hi]);
+        let (v0, v1) = (table_values[hi - 1], table_values[hi]);
+
+        v0 + (v1 - v0) * (w - w0) / (w1 - w0)
+    })
+}
+
+// sRGB gamma encoding (IEC 61966-2-1), clamped to a valid 8-bit channel
+fn gamma_encode(linear: f64) -> u8 {
+    let c = linear.clamp(0.0, 1.0);
+    let encoded = if c <= 0.0031308 {
+        12.92 * c
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    };
+
+    (encoded * 255.0).round() as u8
+}
+
+// Convert a reflectance spectrum R(λ) to the perceived sRGB color under a chosen illuminant
+// (tabulated on CMF_WAVELENGTHS, e.g. ILLUMINANT_D65), by integrating against the CIE 1931
+// color-matching functions to get XYZ tristimulus values and then applying the standard
+// XYZ→linear-sRGB matrix.
+fn reflectance_to_srgb(reflectance: &Array1<f64>, wavelengths: &Array1<f64>, illuminant_table: &[f64]) -> (u8, u8, u8) {
+    let x_bar = interpolate_table(&CMF_WAVELENGTHS, &CMF_X_BAR, wavelengths, Extrapolation::Zero);
+    let y_bar = interpolate_table(&CMF_WAVELENGTHS, &CMF_Y_BAR, wavelengths, Extrapolation::Zero);
+    let z_bar = interpolate_table(&CMF_WAVELENGTHS, &CMF_Z_BAR, wavelengths, Extrapolation::Zero);
+    let illuminant = interpolate_table(&CMF_WAVELENGTHS, illuminant_table, wavelengths, Extrapolation::Zero);
+
+    let weight = reflectance * &illuminant;
+    let x_raw = (&weight * &x_bar).sum();
+    let y_raw = (&weight * &y_bar).sum();
+    let z_raw = (&weight * &z_bar).sum();
+    let k = 1.0 / (&illuminant * &y_bar).sum();
+
+    let x = k * x_raw;
+    let y = k * y_raw;
+    let z = k * z_raw;
+
+    // XYZ (D65) → linear sRGB
+    let r_lin = 3.2406 * x - 1.5372 * y - 0.4986 * z;
+    let g_lin = -0.9689 * x + 1.8758 * y + 0.0415 * z;
+    let b_lin = 0.0557 * x - 0.2040 * y + 1.0570 * z;
+
+    (gamma_encode(r_lin), gamma_encode(g_lin), gamma_encode(b_lin))
+}
+
+// Load a measured reflectance spectrum from a two-column `wavelength_nm,reflectance` CSV (with
+// a header row), for comparison against the forward model
+fn load_measured_spectrum(path: &str) -> std::io::Result<(Array1<f64>, Array1<f64>)> {
+    let contents = std::fs::read_to_string(path)?;
+    let mut measured_wavelengths = Vec::new();
+    let mut measured_reflectance = Vec::new();
+
+    let malformed_row = |line: &str| {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, format!("malformed row in {path}: {line:?}"))
+    };
+
+    for line in contents.lines().skip(1) {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let mut columns = line.split(',');
+        let w: f64 = columns
+            .next()
+            .ok_or_else(|| malformed_row(line))?
+            .trim()
+            .parse()
+            .map_err(|_| malformed_row(line))?;
+        let r: f64 = columns
+            .next()
+            .ok_or_else(|| malformed_row(line))?
+            .trim()
+            .parse()
+            .map_err(|_| malformed_row(line))?;
 
-chart.configure_mesh().draw().unwrap();
+        measured_wavelengths.push(w);
+        measured_reflectance.push(r);
+    }
 
-for (i, &thickness) in thicknesses.iter().step_by(1000).enumerate() {
-    chart
-        .draw_series(LineSeries::new(
-            wavelengths.iter().zip(reflectivity_spectra.row(i).iter()).map(|(&x, &y)| (x, y)),
-            &Palette99::pick(i),
-        ))
-        .unwrap()
-        .label(format!("Thickness = {} Å", thickness))
-        .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &Palette99::pick(i)));
+    Ok((Array1::from(measured_wavelengths), Array1::from(measured_reflectance)))
 }
 
-chart.configure_series_labels().background_style(&WHITE.mix(0.8)).border_style(&BLACK).draw().unwrap();
\ No newline at end of file
+fn sum_squared_residual(model: &Array1<f64>, measured: &Array1<f64>) -> f64 {
+    (model - measured).mapv(|d| d * d).sum()
+}
+
+// The forward-model inputs held fixed while the fitter searches over thickness
+struct FitContext<'a> {
+    n_incident: Complex64,
+    n_film: &'a Array1<Complex64>,
+    n_substrate: &'a Array1<Complex64>,
+    wavelengths: &'a Array1<f64>,
+    theta0_deg: f64,
+}
+
+// Forward model for a single-film stack (air / thin film / substrate) at a given thickness
+fn single_film_reflectivity(thickness: f64, ctx: &FitContext) -> Array1<f64> {
+    let stack = vec![(ctx.n_film.clone(), thickness)];
+    let (_, _, r_unpolarized) =
+        reflectivity_s_p_unpolarized(ctx.n_incident, &stack, ctx.n_substrate, ctx.wavelengths, ctx.theta0_deg);
+    r_unpolarized
+}
+
+// Coarse global search over the thickness grid, minimizing sum-of-squared residuals against a
+// measured spectrum (already resampled onto `wavelengths`)
+fn coarse_scan_thickness(measured: &Array1<f64>, thicknesses: &Array1<f64>, ctx: &FitContext) -> (f64, f64) {
+    thicknesses
+        .iter()
+        .map(|&thickness| {
+            let model = single_film_reflectivity(thickness, ctx);
+            (thickness, sum_squared_residual(&model, measured))
+        })
+        .fold((0.0, f64::INFINITY), |best, candidate| if candidate.1 < best.1 { candidate } else { best })
+}
+
+// Golden-section refinement of thickness around the coarse-scan minimum
+fn golden_section_refine(
+    measured: &Array1<f64>,
+    mut low: f64,
+    mut high: f64,
+    ctx: &FitContext,
+    tol: f64,
+) -> (f64, f64) {
+    let phi = (5.0_f64.sqrt() - 1.0) / 2.0; // golden ratio conjugate
+
+    let residual_at = |thickness: f64| {
+        let model = single_film_reflectivity(thickness, ctx);
+        sum_squared_residual(&model, measured)
+    };
+
+    let mut c = high - phi * (high - low);
+    let mut d = low + phi * (high - low);
+    let mut fc = residual_at(c);
+    let mut fd = residual_at(d);
+
+    while (high - low).abs() > tol {
+        if fc < fd {
+            high = d;
+            d = c;
+            fd = fc;
+            c = high - phi * (high - low);
+            fc = residual_at(c);
+        } else {
+            low = c;
+            c = d;
+            fc = fd;
+            d = low + phi * (high - low);
+            fd = residual_at(d);
+        }
+    }
+
+    let best_thickness = (low + high) / 2.0;
+    (best_thickness, residual_at(best_thickness))
+}
+
+#[cfg(test)]
+mod fit_tests {
+    use super::*;
+
+    // Self-check: generate a forward spectrum at a known thickness, then recover that thickness
+    // by feeding it back through the same coarse-scan + golden-section pipeline used for real
+    // measured data. This is the only executable regression check on the fitter, since otherwise
+    // it's only exercised by dropping a measured_spectrum.csv into the working directory and reading stdout.
+    #[test]
+    fn recovers_known_thickness_from_its_own_forward_spectrum() {
+        let wavelengths: Array1<f64> = Array::range(400.0, 700.5, 10.0);
+        let n_film: Array1<Complex64> = Array::from_elem(wavelengths.len(), Complex64::new(1.46, 0.0));
+        let n_substrate: Array1<Complex64> = Array::from_elem(wavelengths.len(), Complex64::new(3.5, 0.0));
+
+        let ctx = FitContext {
+            n_incident: Complex64::new(1.0, 0.0),
+            n_film: &n_film,
+            n_substrate: &n_substrate,
+            wavelengths: &wavelengths,
+            theta0_deg: 0.0,
+        };
+
+        let true_thickness = 1234.0;
+        let synthetic_measured = single_film_reflectivity(true_thickness, &ctx);
+
+        let thicknesses: Array1<f64> = Array::range(0.0, 6001.0, 10.0);
+        let (coarse_thickness, _) = coarse_scan_thickness(&synthetic_measured, &thicknesses, &ctx);
+
+        let step = thicknesses[1] - thicknesses[0];
+        let (recovered_thickness, residual) = golden_section_refine(
+            &synthetic_measured,
+            (coarse_thickness - step).max(thicknesses[0]),
+            (coarse_thickness + step).min(thicknesses[thicknesses.len() - 1]),
+            &ctx,
+            1e-3,
+        );
+
+        assert!((recovered_thickness - true_thickness).abs() < 1.0, "recovered {recovered_thickness}");
+        assert!(residual < 1e-9, "residual {residual}");
+    }
+}
+
+fn main() -> std::io::Result<()> {
+    // Define wavelength range (in nm) from 200 to 800, resolution 0.5 nm
+    let wavelengths: Array1<f64> = Array::range(200.0, 800.5, 0.5);
+
+    // Quantify refractive index (n) of the thin film; treated as a transparent dielectric (k = 0).
+    // Use the fused-silica Sellmeier fit rather than the legacy Cauchy fit, since Cauchy breaks down
+    // in the UV where this sweep starts (200 nm). Swap in TIO2_SELLMEIER/SI3N4_SELLMEIER for other
+    // coating materials.
+    let thin_film_dispersion = &SIO2_SELLMEIER;
+    let n_thin_film_re: Array1<f64> = thin_film_dispersion.refractive_index(&wavelengths);
+
+    let k_thin_film: Array1<f64> = Array::zeros(wavelengths.len());
+    let n_thin_film: Array1<Complex64> = complex_index(&n_thin_film_re, &k_thin_film);
+
+    // Refractive index for base material (constant real part for simplicity, with UV absorption)
+    let n_si_re: Array1<f64> = Array::from_elem(wavelengths.len(), 3.5);
+    let k_si: Array1<f64> = wavelengths.mapv(silicon_k);
+    let n_substrate: Array1<Complex64> = complex_index(&n_si_re, &k_si);
+
+    // Air
+    let n_air = Complex64::new(1.0, 0.0);
+
+    // Initialize arrays to store reflectivity spectra for different thicknesses
+    let thicknesses: Array1<f64> = Array::range(0.0, 6001.0, 1.0); // Thickness range from 0 Å to 6000 Å in steps of 1 Å
+    let mut reflectivity_spectra: Array2<f64> = Array2::zeros((thicknesses.len(), wavelengths.len()));
+
+    // Normal incidence, unpolarized (s and p coincide at θ0 = 0)
+    let theta0_deg = 0.0;
+
+    // Calculate reflectivity spectra for each thickness (single-film stack: air / thin film / silicon)
+    for (i, &thickness) in thicknesses.iter().enumerate() {
+        let stack = vec![(n_thin_film.clone(), thickness)];
+        let (_, _, r_unpolarized) =
+            reflectivity_s_p_unpolarized(n_air, &stack, &n_substrate, &wavelengths, theta0_deg);
+        reflectivity_spectra.row_mut(i).assign(&r_unpolarized);
+    }
+
+    // Plot reflectivity spectra for selected thicknesses
+    let root = BitMapBackend::new("reflectivity_spectra.png", (1200, 800)).into_drawing_area();
+    root.fill(&WHITE).unwrap();
+    let mut chart = ChartBuilder::on(&root)
+        .caption("Reflectivity Spectra of stack", ("sans-serif", 50).into_font())
+        .margin(10)
+        .x_label_area_size(30)
+        .y_label_area_size(30)
+        .build_cartesian_2d(200.0..800.0, 0.0..1.0)
+        .unwrap();
+
+    chart.configure_mesh().draw().unwrap();
+
+    for (i, &thickness) in thicknesses.iter().step_by(1000).enumerate() {
+        chart
+            .draw_series(LineSeries::new(
+                wavelengths.iter().zip(reflectivity_spectra.row(i * 1000).iter()).map(|(&x, &y)| (x, y)),
+                &Palette99::pick(i),
+            ))
+            .unwrap()
+            .label(format!("Thickness = {} Å", thickness))
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &Palette99::pick(i)));
+    }
+
+    chart.configure_series_labels().background_style(&WHITE.mix(0.8)).border_style(&BLACK).draw().unwrap();
+
+    // Render the perceived interference color at each thickness as a horizontal strip, so the
+    // 0–6000 Å sweep shows the familiar oxide color progression at a glance
+    let strip_width = thicknesses.len() as u32;
+    let strip_height = 120;
+    let strip_root = BitMapBackend::new("thickness_vs_color.png", (strip_width, strip_height)).into_drawing_area();
+    strip_root.fill(&WHITE).unwrap();
+
+    for (i, _) in thicknesses.iter().enumerate() {
+        let (r, g, b) = reflectance_to_srgb(&reflectivity_spectra.row(i).to_owned(), &wavelengths, &ILLUMINANT_D65);
+        strip_root
+            .draw(&Rectangle::new(
+                [(i as i32, 0), (i as i32 + 1, strip_height as i32)],
+                RGBColor(r, g, b).filled(),
+            ))
+            .unwrap();
+    }
+
+    strip_root.present().unwrap();
+
+    // Inverse fitting: recover the film thickness from a measured spectrum, if one is available
+    let measured_path = "measured_spectrum.csv";
+
+    if let Ok((measured_wavelengths, measured_reflectance)) = load_measured_spectrum(measured_path) {
+        let measured_on_grid = interpolate_table(
+            measured_wavelengths.as_slice().unwrap(),
+            measured_reflectance.as_slice().unwrap(),
+            &wavelengths,
+            Extrapolation::Clamp,
+        );
+
+        let fit_context = FitContext {
+            n_incident: n_air,
+            n_film: &n_thin_film,
+            n_substrate: &n_substrate,
+            wavelengths: &wavelengths,
+            theta0_deg,
+        };
+
+        let (coarse_thickness, _) = coarse_scan_thickness(&measured_on_grid, &thicknesses, &fit_context);
+
+        // Refine within one coarse-scan step on either side of the global minimum
+        let step = thicknesses[1] - thicknesses[0];
+        let (best_thickness, best_residual) = golden_section_refine(
+            &measured_on_grid,
+            (coarse_thickness - step).max(thicknesses[0]),
+            (coarse_thickness + step).min(thicknesses[thicknesses.len() - 1]),
+            &fit_context,
+            1e-3,
+        );
+
+        println!("Best-fit thickness: {:.3} Å (residual = {:.6})", best_thickness, best_residual);
+
+        // Overlay the measured spectrum against the best-fit model
+        let fitted = single_film_reflectivity(best_thickness, &fit_context);
+
+        let overlay_root = BitMapBackend::new("measured_vs_fitted.png", (1200, 800)).into_drawing_area();
+        overlay_root.fill(&WHITE).unwrap();
+        let mut overlay_chart = ChartBuilder::on(&overlay_root)
+            .caption("Measured vs. Fitted Reflectivity", ("sans-serif", 50).into_font())
+            .margin(10)
+            .x_label_area_size(30)
+            .y_label_area_size(30)
+            .build_cartesian_2d(200.0..800.0, 0.0..1.0)
+            .unwrap();
+
+        overlay_chart.configure_mesh().draw().unwrap();
+
+        overlay_chart
+            .draw_series(LineSeries::new(
+                wavelengths.iter().zip(measured_on_grid.iter()).map(|(&x, &y)| (x, y)),
+                RED,
+            ))
+            .unwrap()
+            .label("Measured")
+            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], RED));
+
+        overlay_chart
+            .draw_series(LineSeries::new(wavelengths.iter().zip(fitted.iter()).map(|(&x, &y)| (x, y)), BLUE))
+            .unwrap()
+            .label("Fitted")
+            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLUE));
+
+        overlay_chart.configure_series_labels().background_style(WHITE.mix(0.8)).border_style(BLACK).draw().unwrap();
+    } else {
+        println!("No measured spectrum found at {measured_path}; skipping inverse fit.");
+    }
+
+    Ok(())
+}